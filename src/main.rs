@@ -1,18 +1,22 @@
 /*
 TODO:
-- better argument parsing and more arguments (target directory, concurrent number of requests)
 - better error messages
-- Try using HEAD request to determine size of media instead of the json size field because
-  the latter is sometimes incorrect.
 */
 
 use anyhow::{anyhow, Context, Result};
+use byte_unit::Byte;
 use clap::Parser;
-use futures::stream::{StreamExt, TryStreamExt};
+use futures::stream::StreamExt;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use rand::Rng;
 use reqwest::Client;
-use serde::Deserialize;
-use std::path::{Path, PathBuf};
-use tokio::{fs, io};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+use tokio::{fs, io::AsyncWriteExt, sync::Mutex};
 
 fn is_ascii_alphanumeric(s: &str) -> bool {
     s.chars().all(|char| char.is_ascii_alphanumeric())
@@ -35,6 +39,14 @@ fn parse_album_argument(s: &str) -> Result<String> {
         .ok_or_else(|| anyhow!("invalid album"))
 }
 
+fn parse_jobs(s: &str) -> Result<usize> {
+    let jobs: usize = s.parse().with_context(|| format!("invalid jobs: {}", s))?;
+    if jobs == 0 {
+        return Err(anyhow!("jobs must be at least 1"));
+    }
+    Ok(jobs)
+}
+
 #[derive(Debug, Deserialize)]
 struct AlbumResponse {
     media: Vec<MediaResponse>,
@@ -45,24 +57,115 @@ struct MediaResponse {
     url: String,
     ext: String,
     size: u64,
+    name: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+/// Tracks imgur's credit-based rate limit and pauses new requests once it is close to
+/// being exhausted, instead of letting the server throttle or block us mid-run.
+struct RateLimiter {
+    paused_until: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    const LOW_CREDITS_THRESHOLD: u32 = 10;
+    const DEFAULT_PAUSE: Duration = Duration::from_secs(60);
+
+    fn new() -> Self {
+        Self {
+            paused_until: Mutex::new(None),
+        }
+    }
+
+    /// Sleeps until any previously observed rate limit has reset.
+    async fn wait(&self) {
+        let paused_until = *self.paused_until.lock().await;
+        if let Some(paused_until) = paused_until {
+            let now = Instant::now();
+            if paused_until > now {
+                tokio::time::sleep(paused_until - now).await;
+            }
+        }
+    }
+
+    /// Inspects a response's rate limit headers and starts pausing future requests if
+    /// credits are running low or the server sent a 429. The pause lasts `Retry-After`
+    /// seconds when that header is present and `DEFAULT_PAUSE` otherwise.
+    async fn observe(&self, response: &reqwest::Response) {
+        let headers = response.headers();
+        let remaining: Option<u32> = headers
+            .get("X-RateLimit-ClientRemaining")
+            .or_else(|| headers.get("X-RateLimit-UserRemaining"))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+        let retry_after: Option<u64> = headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+
+        let low_on_credits =
+            remaining.is_some_and(|remaining| remaining < Self::LOW_CREDITS_THRESHOLD);
+        let pause = (response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS || low_on_credits)
+            .then(|| retry_after.map_or(Self::DEFAULT_PAUSE, Duration::from_secs));
+
+        if let Some(pause) = pause {
+            *self.paused_until.lock().await = Some(Instant::now() + pause);
+        }
+    }
+
+    /// Whether a previously observed rate limit is still in effect.
+    async fn is_paused(&self) -> bool {
+        let paused_until = *self.paused_until.lock().await;
+        paused_until.is_some_and(|paused_until| paused_until > Instant::now())
+    }
 }
 
-async fn get_album(id: &str, client: &Client) -> Result<AlbumResponse> {
+async fn get_album(id: &str, client: &Client, rate_limiter: &RateLimiter) -> Result<AlbumResponse> {
     let url = format!(
         "https://api.imgur.com/post/v1/albums/{}?client_id=546c25a59c58ad7&include=media",
         id
     );
-    let response = client.get(url.as_str()).send().await?.error_for_status()?;
+    rate_limiter.wait().await;
+    let response = client.get(url.as_str()).send().await?;
+    rate_limiter.observe(&response).await;
+    let response = response.error_for_status()?;
     response.json().await.map_err(Into::into)
 }
 
-async fn get_media(media: &MediaResponse, client: &Client) -> Result<impl io::AsyncRead> {
+async fn get_media(
+    media: &MediaResponse,
+    client: &Client,
+    rate_limiter: &RateLimiter,
+) -> Result<impl futures::Stream<Item = reqwest::Result<bytes::Bytes>>> {
+    rate_limiter.wait().await;
     let response = client.get(media.url.as_str()).send().await?;
-    let stream = response.bytes_stream();
-    let reader = tokio_util::io::StreamReader::new(
-        stream.map_err(|err| io::Error::new(io::ErrorKind::Other, err)),
-    );
-    Ok(reader)
+    rate_limiter.observe(&response).await;
+    let response = response.error_for_status()?;
+    Ok(response.bytes_stream())
+}
+
+/// Returns the `Content-Length` reported by a HEAD request, if the server sends one.
+///
+/// Imgur's JSON `size` field is sometimes wrong, so callers should prefer this over
+/// `MediaResponse::size` when it is available. This costs an extra request per file, so
+/// callers should skip it once the rate limiter is already pausing requests.
+async fn content_length(
+    url: &str,
+    client: &Client,
+    rate_limiter: &RateLimiter,
+) -> Result<Option<u64>> {
+    rate_limiter.wait().await;
+    let response = client.head(url).send().await?;
+    rate_limiter.observe(&response).await;
+    let response = response.error_for_status()?;
+    Ok(response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok()))
 }
 
 fn digits_in_decmial_representation(n: usize) -> usize {
@@ -72,55 +175,311 @@ fn digits_in_decmial_representation(n: usize) -> usize {
     ((n as f32).log10() + 1.0).floor() as usize
 }
 
-fn file_name(media: &MediaResponse, index: usize, media_count: usize) -> PathBuf {
+/// Turns a title into a filesystem- and shell-friendly slug, e.g. "My Cool Meme!" -> "my-cool-meme".
+fn slugify(s: &str) -> String {
+    let mut slug = String::with_capacity(s.len());
+    for part in s.split(|c: char| !c.is_ascii_alphanumeric()) {
+        if part.is_empty() {
+            continue;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&part.to_ascii_lowercase());
+    }
+    slug
+}
+
+fn file_name(media: &MediaResponse, index: usize, media_count: usize, with_title: bool) -> PathBuf {
     assert!(index < media_count);
     let max_digits = digits_in_decmial_representation(media_count - 1);
     let index_digits = digits_in_decmial_representation(index);
     let leading_zeroes = max_digits - index_digits;
-    let name = format!("{}{}.{}", "0".repeat(leading_zeroes), index, media.ext);
+    let slug = with_title
+        .then(|| media.title.as_deref().or(media.name.as_deref()))
+        .flatten()
+        .map(slugify)
+        .filter(|slug| !slug.is_empty());
+    let name = match slug {
+        Some(slug) => format!(
+            "{}{}-{}.{}",
+            "0".repeat(leading_zeroes),
+            index,
+            slug,
+            media.ext
+        ),
+        None => format!("{}{}.{}", "0".repeat(leading_zeroes), index, media.ext),
+    };
     PathBuf::from(name)
 }
 
-async fn download_media(media: &MediaResponse, destination: &Path, client: &Client) -> Result<()> {
+#[derive(Debug, Serialize)]
+struct MediaMetadata<'a> {
+    url: &'a str,
+    title: Option<&'a str>,
+    description: Option<&'a str>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+/// Writes a `metadata.json` into the album directory recording each file's original url,
+/// title, description and dimensions, keyed by its downloaded file name.
+async fn write_metadata(
+    destination: &Path,
+    media: &[MediaResponse],
+    media_count: usize,
+) -> Result<()> {
+    let mut entries = serde_json::Map::with_capacity(media.len());
+    for (index, media) in media.iter().enumerate() {
+        let file_name = file_name(media, index, media_count, true)
+            .to_string_lossy()
+            .into_owned();
+        let metadata = MediaMetadata {
+            url: media.url.as_str(),
+            title: media.title.as_deref().or(media.name.as_deref()),
+            description: media.description.as_deref(),
+            width: media.width,
+            height: media.height,
+        };
+        entries.insert(file_name, serde_json::to_value(metadata)?);
+    }
+    let json = serde_json::to_string_pretty(&entries)?;
+    fs::write(destination.join("metadata.json"), json).await?;
+    Ok(())
+}
+
+fn progress_style() -> ProgressStyle {
+    ProgressStyle::default_bar()
+        .template("{prefix} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}) {msg}")
+        .progress_chars("#>-")
+}
+
+async fn download_media(
+    media: &MediaResponse,
+    size: u64,
+    destination: &Path,
+    client: &Client,
+    rate_limiter: &RateLimiter,
+    progress_bar: &ProgressBar,
+) -> Result<()> {
     let mut file = fs::OpenOptions::new()
         .create(true)
         .write(true)
         .open(destination)
         .await?;
-    if media.size == file.metadata().await?.len() {
-        println!(
-            "Skipping {} because it has already been downloaded.",
-            media.url
-        );
+    if size == file.metadata().await?.len() {
+        progress_bar.finish_with_message(format!(
+            "skipped {} (already downloaded)",
+            destination.to_string_lossy()
+        ));
         return Ok(());
     }
     file.set_len(0).await?;
+    let mut stream = Box::pin(get_media(media, client, rate_limiter).await?);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("failed to read media chunk")?;
+        file.write_all(&chunk).await?;
+        progress_bar.inc(chunk.len() as u64);
+    }
+    progress_bar.finish_with_message(format!(
+        "done {} ({})",
+        destination.to_string_lossy(),
+        Byte::from_bytes(size as u128).get_appropriate_unit(true)
+    ));
+    Ok(())
+}
+
+/// Whether a failed download is worth retrying (transient network errors, 429, 5xx, or a
+/// body cut short mid-transfer) as opposed to a permanent one (e.g. 404) that would just
+/// waste the remaining attempts.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|err| {
+            err.is_connect()
+                || err.is_timeout()
+                || err.is_body()
+                || err.is_request()
+                || err
+                    .status()
+                    .map(|status| status.as_u16() == 429 || status.is_server_error())
+                    .unwrap_or(false)
+        })
+}
+
+/// Upper bound on the exponential backoff between retries, reached once `attempt` is large
+/// enough that `2u64.pow(attempt - 1)` would otherwise overflow or hammer imgur needlessly.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+async fn download_media_with_retries(
+    media: &MediaResponse,
+    size: u64,
+    destination: &Path,
+    client: &Client,
+    rate_limiter: &RateLimiter,
+    progress_bar: &ProgressBar,
+    retries: u32,
+) -> Result<()> {
+    let mut attempt = 0;
+    loop {
+        match download_media(media, size, destination, client, rate_limiter, progress_bar).await {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < retries && is_retryable(&err) => {
+                attempt += 1;
+                let backoff = Duration::from_millis(500 * 2u64.pow(attempt.min(10) - 1))
+                    .min(MAX_BACKOFF)
+                    + Duration::from_millis(rand::thread_rng().gen_range(0..250));
+                progress_bar.set_position(0);
+                progress_bar.set_message(format!(
+                    "retrying {} ({}/{}): {}",
+                    destination.to_string_lossy(),
+                    attempt,
+                    retries,
+                    err
+                ));
+                tokio::time::sleep(backoff).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Per-file outcome of downloading one album.
+#[derive(Default)]
+struct AlbumSummary {
+    downloaded: usize,
+    failed: usize,
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn download_album(
+    album_id: &str,
+    output_dir: &Path,
+    client: &Client,
+    rate_limiter: &RateLimiter,
+    multi_progress: &MultiProgress,
+    jobs: usize,
+    retries: u32,
+    metadata: bool,
+) -> Result<AlbumSummary> {
+    println!("Retrieving album information for id {}.", album_id);
+    let album = get_album(album_id, client, rate_limiter).await?;
+    let destination = output_dir.join(album_id);
+    let destination = destination.as_path();
+    fs::create_dir_all(destination).await?;
+    let media_count = album.media.len();
     println!(
-        "Downloading {} to {}.",
-        media.url,
-        destination.to_string_lossy()
+        "Downloading {} files to directory {}.",
+        media_count,
+        destination.to_string_lossy(),
     );
-    let mut reader = get_media(media, client).await?;
-    io::copy(&mut reader, &mut file).await?;
-    Ok(())
+
+    if metadata {
+        write_metadata(destination, &album.media, media_count).await?;
+    }
+
+    let style = progress_style();
+    let index_digits = digits_in_decmial_representation(media_count.max(1) - 1);
+    let downloaded = AtomicUsize::new(0);
+    let failed = AtomicUsize::new(0);
+
+    let media = futures::stream::iter(album.media.into_iter().enumerate());
+    media
+        .for_each_concurrent(jobs, |(index, media)| {
+            let downloaded = &downloaded;
+            let failed = &failed;
+            let style = style.clone();
+            async move {
+                let mut path = destination.to_path_buf();
+                path.push(file_name(&media, index, media_count, metadata));
+
+                // Skip the extra HEAD request once we are already pausing for rate limit
+                // credits, so this doesn't burn through them twice as fast.
+                let size = if rate_limiter.is_paused().await {
+                    media.size
+                } else {
+                    content_length(media.url.as_str(), client, rate_limiter)
+                        .await
+                        .ok()
+                        .flatten()
+                        .unwrap_or(media.size)
+                };
+
+                let progress_bar = multi_progress.add(ProgressBar::new(size));
+                progress_bar.set_style(style);
+                progress_bar.set_prefix(format!("[{:>width$}]", index, width = index_digits));
+
+                match download_media_with_retries(
+                    &media,
+                    size,
+                    path.as_path(),
+                    client,
+                    rate_limiter,
+                    &progress_bar,
+                    retries,
+                )
+                .await
+                {
+                    Ok(()) => {
+                        downloaded.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(err) => {
+                        failed.fetch_add(1, Ordering::Relaxed);
+                        progress_bar.abandon_with_message(format!(
+                            "failed to download {} to {}: {:?}",
+                            media.url,
+                            path.to_string_lossy(),
+                            err
+                        ));
+                    }
+                }
+            }
+        })
+        .await;
+
+    Ok(AlbumSummary {
+        downloaded: downloaded.into_inner(),
+        failed: failed.into_inner(),
+    })
 }
 
 /// download imgur albums and galleries
 ///
-/// The album is downloaded into a directory named after the album id.
+/// Each album is downloaded into a directory named after the album id.
 /// Files are named after their position in the album.
 /// Existing files are skipped if they have the correct size as reported by imgur.
 #[derive(Debug, Parser)]
 #[clap(version)]
 struct Args {
-    /// the album or gallery id or full url
+    /// the albums or galleries to download, as ids or full urls
     ///
     /// Examples:
     /// - vNOUshX
     /// - https://imgur.com/a/vNOUshX
     /// - https://imgur.com/gallery/vNOUshX
     #[clap(verbatim_doc_comment, parse(try_from_str = parse_album_argument))]
-    album: String,
+    albums: Vec<String>,
+
+    /// read additional album ids or urls from this file, one per line
+    #[clap(short, long)]
+    input_file: Option<PathBuf>,
+
+    /// number of files to download concurrently
+    #[clap(short, long, default_value = "2", parse(try_from_str = parse_jobs))]
+    jobs: usize,
+
+    /// directory to create the album directories in
+    #[clap(short, long, default_value = ".")]
+    output_dir: PathBuf,
+
+    /// number of times to retry a file after a transient failure
+    #[clap(long, default_value = "3")]
+    retries: u32,
+
+    /// write a metadata.json with each file's title, description and dimensions, and
+    /// include a slugified title in downloaded file names
+    #[clap(long)]
+    metadata: bool,
 }
 
 fn main() -> Result<()> {
@@ -133,47 +492,84 @@ fn main() -> Result<()> {
 }
 
 async fn main_(args: Args) -> Result<()> {
-    let album_id = args.album.as_str();
-    let client = Client::builder()
-        .build()
-        .context("failed to create reqwest client")?;
-    println!("Retrieving album information for id {}.", album_id);
-    let album = get_album(album_id, &client).await?;
-    let destination = Path::new(album_id);
-    fs::create_dir_all(destination).await?;
-    let media_count = album.media.len();
-    println!(
-        "Downloading {} files to directory {}.",
-        media_count,
-        destination.to_string_lossy(),
-    );
-
-    let media = futures::stream::iter(album.media.into_iter().enumerate());
-    media
-        .for_each_concurrent(2, |(index, media)| {
-            let client = &client;
-            async move {
-                let mut path = destination.to_path_buf();
-                path.push(file_name(&media, index, media_count));
-                if let Err(err) = download_media(&media, path.as_path(), client).await {
+    let mut album_ids = args.albums.clone();
+    let mut invalid_album_lines = 0;
+    if let Some(input_file) = &args.input_file {
+        let contents = fs::read_to_string(input_file)
+            .await
+            .with_context(|| format!("failed to read {}", input_file.to_string_lossy()))?;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match parse_album_argument(line) {
+                Ok(album_id) => album_ids.push(album_id),
+                Err(err) => {
+                    invalid_album_lines += 1;
                     println!(
-                        "Failed to download {} to {}: {:?}.",
-                        media.url,
-                        path.to_string_lossy(),
+                        "Skipping invalid line in {}: {:?}",
+                        input_file.to_string_lossy(),
                         err
                     );
                 }
             }
-        })
-        .await;
+        }
+    }
+    if album_ids.is_empty() {
+        return Err(anyhow!(
+            "no albums given; pass one or more albums or --input-file"
+        ));
+    }
 
-    println!("Done");
+    let client = Client::builder()
+        .build()
+        .context("failed to create reqwest client")?;
+    let rate_limiter = RateLimiter::new();
+    let multi_progress = MultiProgress::new();
+
+    let mut albums_failed = invalid_album_lines;
+    let mut files_downloaded = 0;
+    let mut files_failed = 0;
+    for album_id in &album_ids {
+        match download_album(
+            album_id,
+            &args.output_dir,
+            &client,
+            &rate_limiter,
+            &multi_progress,
+            args.jobs,
+            args.retries,
+            args.metadata,
+        )
+        .await
+        {
+            Ok(summary) => {
+                files_downloaded += summary.downloaded;
+                files_failed += summary.failed;
+            }
+            Err(err) => {
+                albums_failed += 1;
+                println!("Failed to process album {}: {:?}", album_id, err);
+            }
+        }
+    }
+
+    let total_albums = album_ids.len() + invalid_album_lines;
+    println!(
+        "Done. {}/{} albums processed successfully, {} files downloaded, {} files failed.",
+        total_albums - albums_failed,
+        total_albums,
+        files_downloaded,
+        files_failed,
+    );
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
     #[test]
     fn extract_album_id_() {
@@ -198,4 +594,112 @@ mod tests {
         assert_eq!(digits_in_decmial_representation(99), 2);
         assert_eq!(digits_in_decmial_representation(100), 3);
     }
+
+    /// Spawns a throwaway server that replies with `response_head` to a single request and
+    /// returns the client's response to it.
+    async fn fake_response(response_head: &str) -> reqwest::Response {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let response_head = response_head.to_string();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            socket.write_all(response_head.as_bytes()).await.unwrap();
+        });
+        Client::new()
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Builds on `fake_response` to return the `error_for_status` error a client sees for a
+    /// given status line.
+    async fn status_error(status_line: &str) -> anyhow::Error {
+        let head = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status_line);
+        let response = fake_response(&head).await;
+        anyhow::Error::from(response.error_for_status().unwrap_err())
+    }
+
+    #[tokio::test]
+    async fn is_retryable_connect_error_() {
+        // Nothing listens on this port, so the connection is refused immediately.
+        let err = Client::new()
+            .get("http://127.0.0.1:1")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(is_retryable(&anyhow::Error::from(err)));
+    }
+
+    #[tokio::test]
+    async fn is_retryable_429_and_5xx_() {
+        assert!(is_retryable(&status_error("429 Too Many Requests").await));
+        assert!(is_retryable(&status_error("503 Service Unavailable").await));
+    }
+
+    #[tokio::test]
+    async fn is_retryable_404_is_permanent_() {
+        assert!(!is_retryable(&status_error("404 Not Found").await));
+    }
+
+    #[tokio::test]
+    async fn is_retryable_truncated_body_() {
+        // Promise more bytes than are actually sent, then close the connection, simulating
+        // a download getting cut off partway through.
+        let response = fake_response("HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\nshort").await;
+        let mut stream = response.bytes_stream();
+        let mut err = None;
+        while let Some(chunk) = stream.next().await {
+            if let Err(chunk_err) = chunk {
+                err = Some(chunk_err);
+            }
+        }
+        let err = err.expect("truncated body should produce a stream error");
+        assert!(err.is_body());
+        assert!(is_retryable(&anyhow::Error::from(err)));
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_observe_low_credits_pauses_() {
+        let response = fake_response(
+            "HTTP/1.1 200 OK\r\nX-RateLimit-ClientRemaining: 5\r\nContent-Length: 0\r\n\r\n",
+        )
+        .await;
+        let rate_limiter = RateLimiter::new();
+        rate_limiter.observe(&response).await;
+        assert!(rate_limiter.is_paused().await);
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_observe_429_retry_after_pauses_() {
+        let response = fake_response(
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\n\r\n",
+        )
+        .await;
+        let rate_limiter = RateLimiter::new();
+        rate_limiter.observe(&response).await;
+        assert!(rate_limiter.is_paused().await);
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_observe_plenty_of_credits_is_noop_() {
+        let response = fake_response(
+            "HTTP/1.1 200 OK\r\nX-RateLimit-ClientRemaining: 500\r\nContent-Length: 0\r\n\r\n",
+        )
+        .await;
+        let rate_limiter = RateLimiter::new();
+        rate_limiter.observe(&response).await;
+        assert!(!rate_limiter.is_paused().await);
+    }
+
+    #[test]
+    fn slugify_() {
+        assert_eq!(slugify("My Cool Meme!"), "my-cool-meme");
+        assert_eq!(slugify(""), "");
+        assert_eq!(slugify("!!!"), "");
+        assert_eq!(slugify("  leading and trailing  "), "leading-and-trailing");
+        assert_eq!(slugify("héllo wörld"), "h-llo-w-rld");
+    }
 }